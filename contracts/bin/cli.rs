@@ -12,6 +12,26 @@ use odra_cli::{
 };
 use odra::schema::casper_contract_schema::NamedCLType;
 
+/// Escapes a string for embedding in a JSON string literal, per RFC 8259:
+/// the mandatory escapes plus `\u00XX` for the remaining control characters.
+fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
 /// Deploys CasperCreds contract
 pub struct CasperCredsDeployScript;
 
@@ -63,30 +83,181 @@ impl ScenarioMetadata for RegisterIssuerScenario {
     const DESCRIPTION: &'static str = "Register a new credential issuer (owner only)";
 }
 
-/// Scenario to get total credentials count
+/// Scenario to issue a credential to a holder (issuer only)
+pub struct IssueScenario;
+
+impl Scenario for IssueScenario {
+    fn args(&self) -> Vec<CommandArg> {
+        vec![
+            CommandArg::new("holder", "Holder account hash", NamedCLType::Key),
+            CommandArg::new("cred_type", "Credential type", NamedCLType::String),
+            CommandArg::new("title", "Credential title", NamedCLType::String),
+            CommandArg::new("expires_at", "Expiry as unix millis (0 = never)", NamedCLType::U64),
+            CommandArg::new("metadata_hash", "Hash of the off-chain document", NamedCLType::String),
+        ]
+    }
+
+    fn run(
+        &self,
+        env: &HostEnv,
+        container: &DeployedContractsContainer,
+        args: Args
+    ) -> Result<(), Error> {
+        let mut contract = container.contract_ref::<CasperCreds>(env)?;
+        let holder = args.get_single::<Address>("holder")?;
+        let cred_type = args.get_single::<String>("cred_type")?;
+        let title = args.get_single::<String>("title")?;
+        let expires_at = args.get_single::<u64>("expires_at")?;
+        let metadata_hash = args.get_single::<String>("metadata_hash")?;
+
+        env.set_gas(50_000_000_000);
+        let id = contract.try_issue(holder, cred_type, title, expires_at, metadata_hash)?;
+        println!("Issued credential #{}", id);
+        Ok(())
+    }
+}
+
+impl ScenarioMetadata for IssueScenario {
+    const NAME: &'static str = "issue";
+    const DESCRIPTION: &'static str = "Issue a credential to a holder (issuer only)";
+}
+
+/// Scenario to revoke a credential (credential issuer only)
+pub struct RevokeScenario;
+
+impl Scenario for RevokeScenario {
+    fn args(&self) -> Vec<CommandArg> {
+        vec![CommandArg::new("id", "Credential id", NamedCLType::U256)]
+    }
+
+    fn run(
+        &self,
+        env: &HostEnv,
+        container: &DeployedContractsContainer,
+        args: Args
+    ) -> Result<(), Error> {
+        let mut contract = container.contract_ref::<CasperCreds>(env)?;
+        let id = args.get_single::<U256>("id")?;
+
+        env.set_gas(50_000_000_000);
+        contract.try_revoke(id)?;
+        println!("Revoked credential #{}", id);
+        Ok(())
+    }
+}
+
+impl ScenarioMetadata for RevokeScenario {
+    const NAME: &'static str = "revoke";
+    const DESCRIPTION: &'static str = "Revoke a credential (credential issuer only)";
+}
+
+/// Scenario to verify a credential's validity (read-only)
+pub struct VerifyScenario;
+
+impl Scenario for VerifyScenario {
+    fn args(&self) -> Vec<CommandArg> {
+        vec![
+            CommandArg::new("id", "Credential id", NamedCLType::U256),
+            CommandArg::new("json", "Emit the result as JSON", NamedCLType::Bool).optional(),
+        ]
+    }
+
+    fn run(
+        &self,
+        env: &HostEnv,
+        container: &DeployedContractsContainer,
+        args: Args
+    ) -> Result<(), Error> {
+        let contract = container.contract_ref::<CasperCreds>(env)?;
+        let id = args.get_single::<U256>("id")?;
+        let (valid, cred) = contract.verify(id);
+
+        if args.get_single::<bool>("json").unwrap_or(false) {
+            println!(
+                "{{\"id\":\"{}\",\"valid\":{},\"cred_type\":\"{}\",\"title\":\"{}\",\"institution\":\"{}\",\"expires_at\":{}}}",
+                id,
+                valid,
+                json_escape(&cred.cred_type),
+                json_escape(&cred.title),
+                json_escape(&cred.institution),
+                cred.expires_at
+            );
+        } else {
+            println!("Credential #{} valid: {} ({} - {})", id, valid, cred.title, cred.institution);
+        }
+        Ok(())
+    }
+}
+
+impl ScenarioMetadata for VerifyScenario {
+    const NAME: &'static str = "verify";
+    const DESCRIPTION: &'static str = "Verify a credential's validity (read-only)";
+}
+
+/// Scenario to list the credentials held by an address (read-only)
+pub struct GetHolderCredsScenario;
+
+impl Scenario for GetHolderCredsScenario {
+    fn args(&self) -> Vec<CommandArg> {
+        vec![
+            CommandArg::new("holder", "Holder account hash", NamedCLType::Key),
+            CommandArg::new("json", "Emit the result as JSON", NamedCLType::Bool).optional(),
+        ]
+    }
+
+    fn run(
+        &self,
+        env: &HostEnv,
+        container: &DeployedContractsContainer,
+        args: Args
+    ) -> Result<(), Error> {
+        let contract = container.contract_ref::<CasperCreds>(env)?;
+        let holder = args.get_single::<Address>("holder")?;
+        let ids = contract.get_holder_creds(holder);
+
+        if args.get_single::<bool>("json").unwrap_or(false) {
+            let items: Vec<String> = ids.iter().map(|id| format!("\"{}\"", id)).collect();
+            println!("[{}]", items.join(","));
+        } else {
+            println!("Holder owns {} credential(s): {:?}", ids.len(), ids);
+        }
+        Ok(())
+    }
+}
+
+impl ScenarioMetadata for GetHolderCredsScenario {
+    const NAME: &'static str = "get-holder-creds";
+    const DESCRIPTION: &'static str = "List the credentials held by an address (read-only)";
+}
+
+/// Scenario to get total credentials count (read-only)
 pub struct TotalScenario;
 
 impl Scenario for TotalScenario {
     fn args(&self) -> Vec<CommandArg> {
-        vec![]
+        vec![CommandArg::new("json", "Emit the result as JSON", NamedCLType::Bool).optional()]
     }
 
     fn run(
         &self,
         env: &HostEnv,
         container: &DeployedContractsContainer,
-        _args: Args
+        args: Args
     ) -> Result<(), Error> {
         let contract = container.contract_ref::<CasperCreds>(env)?;
         let total: U256 = contract.total();
-        println!("Total credentials issued: {}", total);
+        if args.get_single::<bool>("json").unwrap_or(false) {
+            println!("{{\"total\":\"{}\"}}", total);
+        } else {
+            println!("Total credentials issued: {}", total);
+        }
         Ok(())
     }
 }
 
 impl ScenarioMetadata for TotalScenario {
     const NAME: &'static str = "total";
-    const DESCRIPTION: &'static str = "Get total number of credentials issued";
+    const DESCRIPTION: &'static str = "Get total number of credentials issued (read-only)";
 }
 
 pub fn main() {
@@ -94,7 +265,17 @@ pub fn main() {
         .about("CasperCreds - Decentralized Credential Verification on Casper")
         .deploy(CasperCredsDeployScript)
         .contract::<CasperCreds>()
+        // Mutating scenarios call `set_gas` and submit a signed deploy; the
+        // livenet backend loads the secret key lazily on that first deploy.
         .scenario(RegisterIssuerScenario)
+        .scenario(IssueScenario)
+        .scenario(RevokeScenario)
+        // Read-only scenarios only issue contract queries (no `set_gas`, no
+        // deploy), so the secret key is never loaded — they need no funded
+        // signer. OdraCli does not resolve the key up front; it is read on
+        // demand by the livenet client when a deploy is actually signed.
+        .scenario(VerifyScenario)
+        .scenario(GetHolderCredsScenario)
         .scenario(TotalScenario)
         .build()
         .run();
@@ -1,7 +1,7 @@
 #![cfg_attr(target_arch = "wasm32", no_std)]
 
 use odra::prelude::*;
-use odra::casper_types::U256;
+use odra::casper_types::{U256, U512};
 
 #[odra::odra_type]
 pub struct Credential {
@@ -10,9 +10,38 @@ pub struct Credential {
     pub cred_type: String,
     pub title: String,
     pub institution: String,
+    pub metadata_hash: String,
     pub issued_at: u64,
     pub expires_at: u64,
-    pub revoked: bool,
+}
+
+/// Describes the expected shape of a credential type, anchoring off-chain
+/// documents to an agreed set of fields and version.
+#[odra::odra_type]
+pub struct SchemaDef {
+    pub version: u32,
+    pub fields: Vec<String>,
+}
+
+#[odra::event]
+pub struct IssuerRegistered {
+    pub issuer: Address,
+    pub name: String,
+}
+
+#[odra::event]
+pub struct CredentialIssued {
+    pub id: U256,
+    pub issuer: Address,
+    pub holder: Address,
+    pub cred_type: String,
+    pub expires_at: u64,
+}
+
+#[odra::event]
+pub struct CredentialRevoked {
+    pub id: U256,
+    pub issuer: Address,
 }
 
 #[odra::odra_error]
@@ -23,6 +52,12 @@ pub enum Error {
     NotFound = 4,
     AlreadyRevoked = 5,
     IssuerExists = 6,
+    NotTokenOwner = 7,
+    NotApprovedOrOwner = 8,
+    InsufficientFee = 9,
+    InsufficientBalance = 10,
+    NonPayable = 11,
+    SchemaNotFound = 12,
 }
 
 #[odra::module]
@@ -31,7 +66,18 @@ pub struct CasperCreds {
     issuers: Mapping<Address, (String, bool)>,
     credentials: Mapping<U256, Credential>,
     cred_count: Var<U256>,
-    holder_creds: Mapping<Address, Vec<U256>>,
+    // CEP-78 style token ownership: custody of a credential token can move to
+    // the holder independently of the `issuer` recorded in its metadata.
+    token_owner: Mapping<U256, Address>,
+    token_approved: Mapping<U256, Option<Address>>,
+    owned_tokens: Mapping<Address, Vec<U256>>,
+    // Revocation status list: word `k` packs the status bits for credential IDs
+    // `[k*256, k*256+256)`; credential `id` uses bit `id % 256` of word `id / 256`.
+    status_list: Mapping<U256, U256>,
+    // Per-issuance fee and the owner-withdrawable balance it accrues into.
+    fee: Var<U512>,
+    balance: Var<U512>,
+    schemas: Mapping<String, SchemaDef>,
 }
 
 #[odra::module]
@@ -46,7 +92,8 @@ impl CasperCreds {
         if self.issuers.get(&issuer).is_some() {
             self.env().revert(Error::IssuerExists);
         }
-        self.issuers.set(&issuer, (name, true));
+        self.issuers.set(&issuer, (name.clone(), true));
+        self.env().emit_event(IssuerRegistered { issuer, name });
     }
 
     pub fn deactivate_issuer(&mut self, issuer: Address) {
@@ -56,6 +103,26 @@ impl CasperCreds {
         }
     }
 
+    pub fn register_schema(&mut self, cred_type: String, schema: SchemaDef) {
+        let caller = self.env().caller();
+        let is_issuer = matches!(self.issuers.get(&caller), Some((_, true)));
+        if caller != self.owner.get().unwrap() && !is_issuer {
+            self.env().revert(Error::NotIssuer);
+        }
+        self.schemas.set(&cred_type, schema);
+    }
+
+    pub fn get_schema(&self, cred_type: String) -> Option<SchemaDef> {
+        self.no_value();
+        self.schemas.get(&cred_type)
+    }
+
+    pub fn set_fee(&mut self, amount: U512) {
+        self.only_owner();
+        self.fee.set(amount);
+    }
+
+    #[odra(payable)]
     pub fn issue(
         &mut self,
         holder: Address,
@@ -69,63 +136,180 @@ impl CasperCreds {
             .unwrap_or_else(|| self.env().revert(Error::NotIssuer));
         if !active { self.env().revert(Error::NotIssuer); }
 
+        if self.schemas.get(&credential_type).is_none() {
+            self.env().revert(Error::SchemaNotFound);
+        }
+
+        let attached = self.env().attached_value();
+        if attached < self.fee.get_or_default() {
+            self.env().revert(Error::InsufficientFee);
+        }
+        self.balance.set(self.balance.get_or_default() + attached);
+
         let id = self.cred_count.get_or_default();
         let cred = Credential {
             issuer: caller,
             holder,
-            cred_type: credential_type,
+            cred_type: credential_type.clone(),
             title,
             institution,
+            metadata_hash,
             issued_at: self.env().get_block_time(),
             expires_at,
-            revoked: false,
         };
 
         self.credentials.set(&id, cred);
         self.cred_count.set(id + 1);
 
-        // Index by holder
-        let mut list = self.holder_creds.get(&holder).unwrap_or_default();
+        // Mint the token to the holder and index it under current custody.
+        self.token_owner.set(&id, holder);
+        let mut list = self.owned_tokens.get(&holder).unwrap_or_default();
         list.push(id);
-        self.holder_creds.set(&holder, list);
+        self.owned_tokens.set(&holder, list);
+
+        self.env().emit_event(CredentialIssued {
+            id,
+            issuer: caller,
+            holder,
+            cred_type: credential_type,
+            expires_at,
+        });
 
         id
     }
 
     pub fn revoke(&mut self, id: U256) {
         let caller = self.env().caller();
-        let mut cred = self.credentials.get(&id)
+        let cred = self.credentials.get(&id)
             .unwrap_or_else(|| self.env().revert(Error::NotFound));
         if cred.issuer != caller { self.env().revert(Error::NotCredIssuer); }
-        if cred.revoked { self.env().revert(Error::AlreadyRevoked); }
-        cred.revoked = true;
-        self.credentials.set(&id, cred);
+
+        let (word_key, mask) = Self::status_slot(id);
+        let word = self.status_list.get(&word_key).unwrap_or_default();
+        if word & mask != U256::zero() { self.env().revert(Error::AlreadyRevoked); }
+        self.status_list.set(&word_key, word | mask);
+
+        self.env().emit_event(CredentialRevoked { id, issuer: caller });
+    }
+
+    pub fn batch_revoke(&mut self, ids: Vec<U256>) {
+        let caller = self.env().caller();
+        // Coalesce every ID into at most one write per 256-ID word.
+        let mut pending: Vec<(U256, U256)> = Vec::new();
+        for id in ids {
+            let cred = self.credentials.get(&id)
+                .unwrap_or_else(|| self.env().revert(Error::NotFound));
+            if cred.issuer != caller { self.env().revert(Error::NotCredIssuer); }
+            let (word_key, mask) = Self::status_slot(id);
+            match pending.iter_mut().find(|(k, _)| *k == word_key) {
+                Some(entry) => entry.1 |= mask,
+                None => pending.push((word_key, mask)),
+            }
+            self.env().emit_event(CredentialRevoked { id, issuer: caller });
+        }
+        for (word_key, mask) in pending {
+            let word = self.status_list.get(&word_key).unwrap_or_default();
+            self.status_list.set(&word_key, word | mask);
+        }
+    }
+
+    pub fn withdraw(&mut self, amount: U512) {
+        self.only_owner();
+        let balance = self.balance.get_or_default();
+        if amount > balance { self.env().revert(Error::InsufficientBalance); }
+        self.balance.set(balance - amount);
+        let owner = self.owner.get().unwrap();
+        self.env().transfer_tokens(&owner, &amount);
+    }
+
+    pub fn is_revoked(&self, id: U256) -> bool {
+        let (word_key, mask) = Self::status_slot(id);
+        let word = self.status_list.get(&word_key).unwrap_or_default();
+        word & mask != U256::zero()
+    }
+
+    pub fn owner_of(&self, id: U256) -> Address {
+        self.token_owner.get(&id)
+            .unwrap_or_else(|| self.env().revert(Error::NotFound))
+    }
+
+    pub fn approve(&mut self, id: U256, operator: Address) {
+        let owner = self.owner_of(id);
+        if self.env().caller() != owner { self.env().revert(Error::NotTokenOwner); }
+        self.token_approved.set(&id, Some(operator));
+    }
+
+    pub fn transfer(&mut self, id: U256, to: Address) {
+        let owner = self.owner_of(id);
+        let caller = self.env().caller();
+        let approved = self.token_approved.get(&id).flatten();
+        if caller != owner && approved != Some(caller) {
+            self.env().revert(Error::NotApprovedOrOwner);
+        }
+
+        // Move custody and clear any outstanding approval.
+        self.token_owner.set(&id, to);
+        self.token_approved.set(&id, None);
+
+        let mut from_list = self.owned_tokens.get(&owner).unwrap_or_default();
+        from_list.retain(|t| *t != id);
+        self.owned_tokens.set(&owner, from_list);
+
+        let mut to_list = self.owned_tokens.get(&to).unwrap_or_default();
+        to_list.push(id);
+        self.owned_tokens.set(&to, to_list);
     }
 
     pub fn verify(&self, id: U256) -> (bool, Credential) {
+        self.no_value();
         let cred = self.credentials.get(&id)
             .unwrap_or_else(|| self.env().revert(Error::NotFound));
         let now = self.env().get_block_time();
         let expired = cred.expires_at > 0 && now > cred.expires_at;
-        (!cred.revoked && !expired, cred)
+        (!self.is_revoked(id) && !expired, cred)
+    }
+
+    pub fn verify_with_hash(&self, id: U256, expected_hash: String) -> bool {
+        let (valid, cred) = self.verify(id);
+        valid && cred.metadata_hash == expected_hash
     }
 
     pub fn get_credential(&self, id: U256) -> Option<Credential> {
+        self.no_value();
         self.credentials.get(&id)
     }
 
     pub fn get_holder_creds(&self, holder: Address) -> Vec<U256> {
-        self.holder_creds.get(&holder).unwrap_or_default()
+        self.no_value();
+        self.owned_tokens.get(&holder).unwrap_or_default()
     }
 
     pub fn get_issuer(&self, addr: Address) -> Option<(String, bool)> {
+        self.no_value();
         self.issuers.get(&addr)
     }
 
     pub fn total(&self) -> U256 {
+        self.no_value();
         self.cred_count.get_or_default()
     }
 
+    /// Maps a credential ID to its status-list word key and the single-bit mask
+    /// within that word.
+    fn status_slot(id: U256) -> (U256, U256) {
+        let width = U256::from(256u64);
+        let bit = (id % width).as_usize();
+        (id / width, U256::one() << bit)
+    }
+
+    /// Guards a non-payable entry point: reverts if any motes were attached,
+    /// so view calls cannot accidentally lock CSPR in the contract.
+    fn no_value(&self) {
+        if self.env().attached_value() > U512::zero() {
+            self.env().revert(Error::NonPayable);
+        }
+    }
+
     fn only_owner(&self) {
         if self.env().caller() != self.owner.get().unwrap() {
             self.env().revert(Error::NotOwner);
@@ -149,6 +333,11 @@ mod tests {
         
         env.set_caller(owner);
         c.register_issuer(uni, "MIT".into());
+        c.register_schema("degree".into(), SchemaDef { version: 1, fields: vec![] });
+
+        // Events are read back through the host's off-chain event API — the
+        // non-failing getter pattern an indexer or wallet would use.
+        assert!(env.emitted_event(&c, &IssuerRegistered { issuer: uni, name: "MIT".into() }));
 
         env.set_caller(uni);
         let id = c.issue(student, "degree".into(), "BSc CS".into(), 0, "".into());
@@ -157,8 +346,88 @@ mod tests {
         assert!(valid);
         assert_eq!(cred.title, "BSc CS");
 
+        c.revoke(id);
+        assert!(env.emitted_event(&c, &CredentialRevoked { id, issuer: uni }));
+        let (valid, _) = c.verify(id);
+        assert!(!valid);
+    }
+
+    #[test]
+    fn transfer_moves_custody_not_revocation_rights() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let uni = env.get_account(1);
+        let student = env.get_account(2);
+        let employer = env.get_account(3);
+
+        let mut c = CasperCreds::deploy(&env, NoArgs);
+
+        env.set_caller(owner);
+        c.register_issuer(uni, "MIT".into());
+        c.register_schema("degree".into(), SchemaDef { version: 1, fields: vec![] });
+
+        env.set_caller(uni);
+        let id = c.issue(student, "degree".into(), "BSc CS".into(), 0, "".into());
+        assert_eq!(c.owner_of(id), student);
+
+        // Holder moves custody to an employer; the credential leaves the holder's
+        // listing and appears only in the employer's.
+        env.set_caller(student);
+        c.transfer(id, employer);
+        assert_eq!(c.owner_of(id), employer);
+        assert!(c.get_holder_creds(student).is_empty());
+        assert_eq!(c.get_holder_creds(employer), vec![id]);
+
+        // Revocation authority remains with the original issuer.
+        env.set_caller(uni);
         c.revoke(id);
         let (valid, _) = c.verify(id);
         assert!(!valid);
     }
+
+    #[test]
+    fn batch_revoke_a_cohort() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let uni = env.get_account(1);
+        let student = env.get_account(2);
+
+        let mut c = CasperCreds::deploy(&env, NoArgs);
+
+        env.set_caller(owner);
+        c.register_issuer(uni, "MIT".into());
+        c.register_schema("degree".into(), SchemaDef { version: 1, fields: vec![] });
+
+        env.set_caller(uni);
+        let a = c.issue(student, "degree".into(), "BSc".into(), 0, "".into());
+        let b = c.issue(student, "degree".into(), "MSc".into(), 0, "".into());
+
+        c.batch_revoke(vec![a, b]);
+        assert!(c.is_revoked(a));
+        assert!(c.is_revoked(b));
+        assert!(!c.verify(a).0);
+    }
+
+    #[test]
+    fn metadata_hash_binds_to_schema_conformant_credential() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let uni = env.get_account(1);
+        let student = env.get_account(2);
+
+        let mut c = CasperCreds::deploy(&env, NoArgs);
+
+        env.set_caller(owner);
+        c.register_issuer(uni, "MIT".into());
+        c.register_schema("degree".into(), SchemaDef { version: 1, fields: vec!["gpa".into()] });
+
+        env.set_caller(uni);
+        let id = c.issue(student, "degree".into(), "BSc CS".into(), 0, "0xabc".into());
+        assert!(c.verify_with_hash(id, "0xabc".into()));
+        assert!(!c.verify_with_hash(id, "0xdef".into()));
+
+        // An unregistered credential type cannot be issued.
+        let result = c.try_issue(student, "diploma".into(), "X".into(), 0, "0x1".into());
+        assert_eq!(result, Err(Error::SchemaNotFound.into()));
+    }
 }